@@ -0,0 +1,453 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use cita_trie::DB as TrieDB;
+use rayon::prelude::*;
+
+use protocol::traits::{
+    Context, Executor, ExecutorParams, ExecutorResp, Service, ServiceMapping, ServiceResponse,
+    Storage,
+};
+use protocol::types::{
+    Address, MerkleRoot, Receipt, ServiceContext, ServiceParam, SignedTransaction,
+    TransactionRequest,
+};
+use protocol::ProtocolResult;
+
+use crate::sdk::{DefaultSDKFactory, ReadOnlySDKFactory};
+use crate::state::GeneralServiceState;
+
+pub struct ServiceExecutor<S, DB, Mapping> {
+    trie_db:         Arc<DB>,
+    storage:         Arc<S>,
+    service_mapping: Arc<Mapping>,
+    root:            MerkleRoot,
+}
+
+impl<S, DB, Mapping> ServiceExecutor<S, DB, Mapping>
+where
+    S: Storage + 'static,
+    DB: TrieDB + 'static,
+    Mapping: ServiceMapping + 'static,
+{
+    pub fn create_genesis(
+        services: Vec<ServiceParam>,
+        trie_db: Arc<DB>,
+        storage: Arc<S>,
+        service_mapping: Arc<Mapping>,
+    ) -> ProtocolResult<MerkleRoot> {
+        let mut executor = ServiceExecutor {
+            trie_db,
+            storage,
+            service_mapping,
+            root: MerkleRoot::from_empty(),
+        };
+        executor.settle_genesis(services)?;
+        Ok(executor.root)
+    }
+
+    pub fn with_root(
+        root: MerkleRoot,
+        trie_db: Arc<DB>,
+        storage: Arc<S>,
+        service_mapping: Arc<Mapping>,
+    ) -> ProtocolResult<Self> {
+        Ok(ServiceExecutor {
+            trie_db,
+            storage,
+            service_mapping,
+            root,
+        })
+    }
+
+    fn settle_genesis(&mut self, _services: Vec<ServiceParam>) -> ProtocolResult<()> {
+        // None of the currently registered services need genesis-time
+        // state, so committing the empty state is enough to hand back a
+        // valid starting root.
+        let (root, bytes) = GeneralServiceState::default().commit();
+        GeneralServiceState::persist(self.trie_db.as_ref(), &root, bytes);
+        self.root = root;
+        Ok(())
+    }
+
+    fn snapshot_at(&self, state_root: &MerkleRoot) -> Self {
+        ServiceExecutor {
+            trie_db:         Arc::clone(&self.trie_db),
+            storage:         Arc::clone(&self.storage),
+            service_mapping: Arc::clone(&self.service_mapping),
+            root:            state_root.clone(),
+        }
+    }
+
+    /// Dispatch a single read against an already-loaded state snapshot, so
+    /// callers that need to answer several queries against the same root
+    /// (`read_batch`) only pay for loading that state once.
+    fn dispatch_read(
+        &self,
+        state: &Arc<GeneralServiceState>,
+        caller: &Address,
+        height: u64,
+        request: &TransactionRequest,
+    ) -> ProtocolResult<ServiceResponse<String>> {
+        let factory = ReadOnlySDKFactory::new(Arc::clone(state));
+
+        let service = self
+            .service_mapping
+            .get_service(&request.service_name, &factory)?;
+
+        let ctx = ServiceContext::new(
+            caller.clone(),
+            height,
+            self.root.clone(),
+            request.service_name.clone(),
+            request.method.clone(),
+        );
+
+        Ok(service.read_(ctx, &request.method, &request.payload))
+    }
+
+    /// Evaluate every `(caller, request)` pair against the same
+    /// `params.state_root`, loading that state once and sharing it across
+    /// all items via `Arc` rather than reloading it per query. Each item
+    /// still gets its own `ServiceSDK` view, so the batch runs concurrently
+    /// without a mutable borrow per item.
+    pub fn read_batch(
+        &self,
+        params: &ExecutorParams,
+        requests: &[(Address, TransactionRequest)],
+    ) -> ProtocolResult<Vec<ServiceResponse<String>>> {
+        let snapshot = self.snapshot_at(&params.state_root);
+        let state = Arc::new(GeneralServiceState::load(
+            snapshot.trie_db.as_ref(),
+            &snapshot.root,
+        ));
+
+        requests
+            .par_iter()
+            .map(|(caller, request)| snapshot.dispatch_read(&state, caller, params.height, request))
+            .collect()
+    }
+}
+
+impl<S, DB, Mapping> Executor for ServiceExecutor<S, DB, Mapping>
+where
+    S: Storage + 'static,
+    DB: TrieDB + 'static,
+    Mapping: ServiceMapping + 'static,
+{
+    fn exec(
+        &mut self,
+        _ctx: Context,
+        params: &ExecutorParams,
+        txs: &[SignedTransaction],
+    ) -> ProtocolResult<ExecutorResp> {
+        let state = Rc::new(RefCell::new(GeneralServiceState::load(
+            self.trie_db.as_ref(),
+            &self.root,
+        )));
+        let factory = DefaultSDKFactory::new(Rc::clone(&state));
+
+        let mut receipts = Vec::with_capacity(txs.len());
+        for stx in txs {
+            let request = &stx.raw.request;
+            let mut service = self
+                .service_mapping
+                .get_service(&request.service_name, &factory)?;
+
+            let ctx = ServiceContext::new(
+                stx.raw.sender.clone(),
+                params.height,
+                self.root.clone(),
+                request.service_name.clone(),
+                request.method.clone(),
+            );
+
+            let response = service.write_(ctx, &request.method, &request.payload);
+            receipts.push(Receipt {
+                tx_hash:  stx.tx_hash.clone(),
+                height:   params.height,
+                response,
+            });
+        }
+
+        let (root, bytes) = state.borrow().commit();
+        GeneralServiceState::persist(self.trie_db.as_ref(), &root, bytes);
+        self.root = root.clone();
+
+        Ok(ExecutorResp {
+            state_root: root,
+            receipts,
+        })
+    }
+
+    fn read(
+        &self,
+        params: &ExecutorParams,
+        caller: &Address,
+        height: u64,
+        request: &TransactionRequest,
+    ) -> ProtocolResult<ServiceResponse<String>> {
+        let snapshot = self.snapshot_at(&params.state_root);
+        let state = Arc::new(GeneralServiceState::load(
+            snapshot.trie_db.as_ref(),
+            &snapshot.root,
+        ));
+
+        snapshot.dispatch_read(&state, caller, height, request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use cita_trie::MemoryDB;
+
+    use protocol::traits::{Context as ChainContext, SDKFactory, ServiceSDK};
+    use protocol::types::{Hash, Proof, RawTransaction, Receipt as ReceiptType, SignedTransaction};
+    use protocol::ProtocolResult;
+
+    use super::*;
+
+    struct MockStorage;
+
+    #[async_trait]
+    impl Storage for MockStorage {
+        async fn insert_transactions(
+            &self,
+            _ctx: ChainContext,
+            _block_height: u64,
+            _signed_txs: Vec<SignedTransaction>,
+        ) -> ProtocolResult<()> {
+            unimplemented!()
+        }
+
+        async fn get_transactions(
+            &self,
+            _ctx: ChainContext,
+            _block_height: u64,
+            _hashes: &[Hash],
+        ) -> ProtocolResult<Vec<Option<SignedTransaction>>> {
+            unimplemented!()
+        }
+
+        async fn get_transaction_by_hash(
+            &self,
+            _ctx: ChainContext,
+            _hash: &Hash,
+        ) -> ProtocolResult<Option<SignedTransaction>> {
+            unimplemented!()
+        }
+
+        async fn insert_receipts(
+            &self,
+            _ctx: ChainContext,
+            _block_height: u64,
+            _receipts: Vec<ReceiptType>,
+        ) -> ProtocolResult<()> {
+            unimplemented!()
+        }
+
+        async fn get_receipt_by_hash(
+            &self,
+            _ctx: ChainContext,
+            _hash: Hash,
+        ) -> ProtocolResult<Option<ReceiptType>> {
+            unimplemented!()
+        }
+
+        async fn get_receipts(
+            &self,
+            _ctx: ChainContext,
+            _block_height: u64,
+            _hashes: Vec<Hash>,
+        ) -> ProtocolResult<Vec<Option<ReceiptType>>> {
+            unimplemented!()
+        }
+
+        async fn update_latest_proof(&self, _ctx: ChainContext, _proof: Proof) -> ProtocolResult<()> {
+            unimplemented!()
+        }
+
+        async fn get_latest_proof(&self, _ctx: ChainContext) -> ProtocolResult<Proof> {
+            unimplemented!()
+        }
+    }
+
+    /// A trivial service used only to exercise `ServiceExecutor` dispatch:
+    /// `echo` hands the payload straight back without touching state, and
+    /// `get_stored`/`set_stored` round-trip a value through the service's
+    /// own state namespace.
+    struct EchoService<SDK> {
+        sdk: SDK,
+    }
+
+    impl<SDK: ServiceSDK> Service for EchoService<SDK> {
+        fn write_(
+            &mut self,
+            _ctx: ServiceContext,
+            method: &str,
+            payload: &str,
+        ) -> ServiceResponse<String> {
+            match method {
+                "set_stored" => {
+                    self.sdk.set_value("stored", Bytes::from(payload.to_owned()));
+                    ServiceResponse::from_succeed(payload.to_owned())
+                }
+                _ => ServiceResponse::from_error(404, "method not found".to_owned()),
+            }
+        }
+
+        fn read_(
+            &self,
+            _ctx: ServiceContext,
+            method: &str,
+            payload: &str,
+        ) -> ServiceResponse<String> {
+            match method {
+                "echo" => ServiceResponse::from_succeed(payload.to_owned()),
+                "get_stored" => match self.sdk.get_value("stored") {
+                    Some(bytes) => ServiceResponse::from_succeed(
+                        String::from_utf8(bytes.to_vec()).expect("valid utf8"),
+                    ),
+                    None => ServiceResponse::from_succeed(String::new()),
+                },
+                _ => ServiceResponse::from_error(404, "method not found".to_owned()),
+            }
+        }
+    }
+
+    struct EchoServiceMapping;
+
+    impl ServiceMapping for EchoServiceMapping {
+        fn get_service<SDK: 'static + ServiceSDK, Factory: SDKFactory<SDK>>(
+            &self,
+            name: &str,
+            factory: &Factory,
+        ) -> ProtocolResult<Box<dyn Service>> {
+            match name {
+                "echo" => Ok(Box::new(EchoService {
+                    sdk: factory.get_sdk("echo")?,
+                }) as Box<dyn Service>),
+                _ => panic!("not found service"),
+            }
+        }
+
+        fn list_service_name(&self) -> Vec<String> {
+            vec!["echo".to_owned()]
+        }
+    }
+
+    fn caller() -> Address {
+        Address::from_hex("0xcff1002107105460941f797828f468667aa1a2db").unwrap()
+    }
+
+    fn new_executor() -> (ServiceExecutor<MockStorage, MemoryDB, EchoServiceMapping>, MerkleRoot) {
+        let trie_db = Arc::new(MemoryDB::new(false));
+        let storage = Arc::new(MockStorage);
+        let mapping = Arc::new(EchoServiceMapping);
+
+        let root = ServiceExecutor::create_genesis(
+            vec![],
+            Arc::clone(&trie_db),
+            Arc::clone(&storage),
+            Arc::clone(&mapping),
+        )
+        .unwrap();
+
+        let executor = ServiceExecutor::with_root(root.clone(), trie_db, storage, mapping).unwrap();
+        (executor, root)
+    }
+
+    fn construct_stx(method: &str, payload: &str) -> SignedTransaction {
+        SignedTransaction {
+            raw:       RawTransaction {
+                chain_id:     Hash::from_empty(),
+                nonce:        Hash::from_empty(),
+                timeout:      0,
+                cycles_price: 1,
+                cycles_limit: 1_000_000,
+                request:      TransactionRequest {
+                    service_name: "echo".to_owned(),
+                    method:       method.to_owned(),
+                    payload:      payload.to_owned(),
+                },
+                sender:       caller(),
+            },
+            tx_hash:   Hash::from_empty(),
+            pubkey:    Bytes::new(),
+            signature: Bytes::new(),
+        }
+    }
+
+    fn exec_params(root: MerkleRoot, height: u64) -> ExecutorParams {
+        ExecutorParams {
+            state_root:   root,
+            height,
+            timestamp:    0,
+            cycles_limit: u64::max_value(),
+            proposer:     caller(),
+        }
+    }
+
+    #[test]
+    fn read_batch_returns_each_items_own_response() {
+        let (executor, root) = new_executor();
+        let params = exec_params(root, 1);
+
+        let requests = vec![
+            (caller(), TransactionRequest {
+                service_name: "echo".to_owned(),
+                method:       "echo".to_owned(),
+                payload:      "alice".to_owned(),
+            }),
+            (caller(), TransactionRequest {
+                service_name: "echo".to_owned(),
+                method:       "echo".to_owned(),
+                payload:      "bob".to_owned(),
+            }),
+        ];
+
+        let responses = executor.read_batch(&params, &requests).unwrap();
+
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0].succeed_data, "alice");
+        assert_eq!(responses[1].succeed_data, "bob");
+    }
+
+    #[test]
+    fn read_batch_is_pinned_to_the_requested_state_root_not_the_live_one() {
+        let (mut executor, genesis_root) = new_executor();
+
+        let first_resp = executor
+            .exec(ChainContext::new(), &exec_params(genesis_root, 1), &[
+                construct_stx("set_stored", "first"),
+            ])
+            .unwrap();
+        let root_after_first = first_resp.state_root.clone();
+
+        let second_resp = executor
+            .exec(
+                ChainContext::new(),
+                &exec_params(root_after_first.clone(), 2),
+                &[construct_stx("set_stored", "second")],
+            )
+            .unwrap();
+        let root_after_second = second_resp.state_root;
+
+        let get_stored_request = (caller(), TransactionRequest {
+            service_name: "echo".to_owned(),
+            method:       "get_stored".to_owned(),
+            payload:      String::new(),
+        });
+
+        let pinned_to_first =
+            executor.read_batch(&exec_params(root_after_first, 2), &[get_stored_request.clone()]);
+        let pinned_to_second =
+            executor.read_batch(&exec_params(root_after_second, 2), &[get_stored_request]);
+
+        assert_eq!(pinned_to_first.unwrap()[0].succeed_data, "first");
+        assert_eq!(pinned_to_second.unwrap()[0].succeed_data, "second");
+    }
+}
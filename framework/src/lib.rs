@@ -0,0 +1,3 @@
+pub mod executor;
+mod sdk;
+mod state;
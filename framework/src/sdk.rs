@@ -0,0 +1,107 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use bytes::Bytes;
+
+use protocol::traits::{SDKFactory, ServiceSDK};
+use protocol::ProtocolResult;
+
+use crate::state::GeneralServiceState;
+
+/// A `ServiceSDK` backed by the executor's shared service state. Every
+/// service gets its own namespace within the same underlying map, so
+/// reads/writes from one service never shadow another's.
+pub struct DefaultServiceSDK {
+    service_name: String,
+    state:        Rc<RefCell<GeneralServiceState>>,
+}
+
+impl DefaultServiceSDK {
+    pub fn new(service_name: String, state: Rc<RefCell<GeneralServiceState>>) -> Self {
+        Self {
+            service_name,
+            state,
+        }
+    }
+}
+
+impl ServiceSDK for DefaultServiceSDK {
+    fn get_value(&self, key: &str) -> Option<Bytes> {
+        self.state.borrow().get_value(&self.service_name, key)
+    }
+
+    fn set_value(&mut self, key: &str, value: Bytes) {
+        self.state
+            .borrow_mut()
+            .set_value(&self.service_name, key, value);
+    }
+}
+
+/// Hands every service in the mapping its own `DefaultServiceSDK` view over
+/// one shared state snapshot, so `ServiceMapping::get_service` builds the
+/// whole service graph against a single, consistent root.
+pub struct DefaultSDKFactory {
+    state: Rc<RefCell<GeneralServiceState>>,
+}
+
+impl DefaultSDKFactory {
+    pub fn new(state: Rc<RefCell<GeneralServiceState>>) -> Self {
+        Self { state }
+    }
+}
+
+impl SDKFactory<DefaultServiceSDK> for DefaultSDKFactory {
+    fn get_sdk(&self, name: &str) -> ProtocolResult<DefaultServiceSDK> {
+        Ok(DefaultServiceSDK::new(
+            name.to_owned(),
+            Rc::clone(&self.state),
+        ))
+    }
+}
+
+/// A read-only `ServiceSDK` view over a state snapshot shared across a
+/// batch of concurrent queries. The snapshot is loaded once per batch and
+/// handed out as an `Arc`, so every item reads it without re-fetching or
+/// re-deserializing the state trie. `set_value` is unreachable from read
+/// dispatch and is a no-op.
+pub struct ReadOnlyServiceSDK {
+    service_name: String,
+    state:        Arc<GeneralServiceState>,
+}
+
+impl ReadOnlyServiceSDK {
+    pub fn new(service_name: String, state: Arc<GeneralServiceState>) -> Self {
+        Self {
+            service_name,
+            state,
+        }
+    }
+}
+
+impl ServiceSDK for ReadOnlyServiceSDK {
+    fn get_value(&self, key: &str) -> Option<Bytes> {
+        self.state.get_value(&self.service_name, key)
+    }
+
+    fn set_value(&mut self, _key: &str, _value: Bytes) {}
+}
+
+pub struct ReadOnlySDKFactory {
+    state: Arc<GeneralServiceState>,
+}
+
+impl ReadOnlySDKFactory {
+    pub fn new(state: Arc<GeneralServiceState>) -> Self {
+        Self { state }
+    }
+}
+
+impl SDKFactory<ReadOnlyServiceSDK> for ReadOnlySDKFactory {
+    fn get_sdk(&self, name: &str) -> ProtocolResult<ReadOnlyServiceSDK> {
+        Ok(ReadOnlyServiceSDK::new(
+            name.to_owned(),
+            Arc::clone(&self.state),
+        ))
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use cita_trie::DB as TrieDB;
+use serde::{Deserialize, Serialize};
+
+use protocol::types::Hash;
+
+/// Keyed byte storage shared by every service in a single state trie,
+/// namespaced per service so two services can't collide on the same key.
+/// Committing folds the whole map into one root hash, so the root is a
+/// pure function of content rather than insertion order.
+#[derive(Default, Serialize, Deserialize)]
+pub struct GeneralServiceState {
+    data: BTreeMap<String, Vec<u8>>,
+}
+
+impl GeneralServiceState {
+    pub fn get_value(&self, service: &str, key: &str) -> Option<Bytes> {
+        self.data
+            .get(&namespaced(service, key))
+            .map(|v| Bytes::from(v.clone()))
+    }
+
+    pub fn set_value(&mut self, service: &str, key: &str, value: Bytes) {
+        self.data.insert(namespaced(service, key), value.to_vec());
+    }
+
+    pub fn commit(&self) -> (Hash, Vec<u8>) {
+        let bytes = serde_json::to_vec(&self.data).expect("serialize service state");
+        let root = Hash::digest(Bytes::from(bytes.clone()));
+        (root, bytes)
+    }
+
+    /// Load the state committed under `root` from `trie_db`, or an empty
+    /// state for the zero root a fresh chain starts from.
+    pub fn load<DB: TrieDB>(trie_db: &DB, root: &Hash) -> Self {
+        if root == &Hash::from_empty() {
+            return Self::default();
+        }
+
+        match trie_db.get(root.as_bytes()).expect("read state trie") {
+            Some(bytes) => serde_json::from_slice(&bytes).expect("decode service state"),
+            None => Self::default(),
+        }
+    }
+
+    pub fn persist<DB: TrieDB>(trie_db: &DB, root: &Hash, bytes: Vec<u8>) {
+        trie_db
+            .insert(root.as_bytes().to_vec(), bytes)
+            .expect("write state trie");
+    }
+}
+
+fn namespaced(service: &str, key: &str) -> String {
+    format!("{}/{}", service, key)
+}
@@ -0,0 +1,16 @@
+/// Unwrap the `succeed_data` of a `ServiceResponse<T>`, or early-return from
+/// the enclosing function with the original error code and message, instead
+/// of discarding it behind a fresh `ServiceResponse::from_error`.
+///
+/// The enclosing function must return `ServiceResponse<R>` for some `R`.
+#[macro_export]
+macro_rules! try_service_response {
+    ($res:expr) => {
+        match $res {
+            res if res.is_error() => {
+                return $crate::traits::ServiceResponse::from_error(res.code, res.error_message);
+            }
+            res => res.succeed_data,
+        }
+    };
+}
@@ -0,0 +1,192 @@
+#[macro_use]
+extern crate serde_derive;
+
+pub mod types;
+
+use derive_more::Display;
+
+use binding_macro::{read, service};
+use common_crypto::{Secp256k1Recoverable, Secp256k1RecoverablePublicKey};
+use protocol::traits::{ServiceResponse, ServiceSDK};
+use protocol::types::{Address, Hash, ServiceContext};
+
+use crate::types::{Keccak256Payload, Keccak256Response, VerifyPayload, VerifyResponse};
+
+pub struct UtilService<SDK> {
+    sdk: SDK,
+}
+
+#[service]
+impl<SDK: ServiceSDK> UtilService<SDK> {
+    pub fn new(sdk: SDK) -> Self {
+        Self { sdk }
+    }
+
+    /// Hash an arbitrary byte string with keccak256.
+    #[read]
+    fn keccak256(
+        &self,
+        _ctx: ServiceContext,
+        payload: Keccak256Payload,
+    ) -> ServiceResponse<Keccak256Response> {
+        let hash = Hash::digest(payload.payload);
+        ServiceResponse::from_succeed(Keccak256Response { hash })
+    }
+
+    /// Recover the signer address from a secp256k1 recoverable `signature`
+    /// over `hash` and compare it against the address derived from
+    /// `pubkey`. Returns an error response instead of panicking when the
+    /// signature or public key bytes are malformed.
+    #[read]
+    fn verify(
+        &self,
+        _ctx: ServiceContext,
+        payload: VerifyPayload,
+    ) -> ServiceResponse<VerifyResponse> {
+        let expected_pubkey = match Secp256k1RecoverablePublicKey::try_from(payload.pubkey.as_ref())
+        {
+            Ok(pubkey) => pubkey,
+            Err(e) => {
+                return ServiceResponse::from_error(
+                    101,
+                    ServiceError::DecodePubkey(e.to_string()).to_string(),
+                )
+            }
+        };
+
+        let recovered_pubkey = match Secp256k1Recoverable::recover_public_key(
+            payload.hash.as_bytes(),
+            payload.signature.as_ref(),
+        ) {
+            Ok(pubkey) => pubkey,
+            Err(e) => {
+                return ServiceResponse::from_error(
+                    102,
+                    ServiceError::DecodeSignature(e.to_string()).to_string(),
+                )
+            }
+        };
+
+        let addresses = (
+            Address::from_pubkey_bytes(recovered_pubkey.to_bytes()),
+            Address::from_pubkey_bytes(expected_pubkey.to_bytes()),
+        );
+        let is_valid = matches!(addresses, (Ok(recovered), Ok(expected)) if recovered == expected);
+
+        ServiceResponse::from_succeed(VerifyResponse { is_valid })
+    }
+}
+
+#[derive(Debug, Display)]
+pub enum ServiceError {
+    #[display(fmt = "decode signature failed {:?}", _0)]
+    DecodeSignature(String),
+
+    #[display(fmt = "decode public key failed {:?}", _0)]
+    DecodePubkey(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use common_crypto::Secp256k1RecoverablePrivateKey;
+    use protocol::types::Address;
+
+    use super::*;
+
+    struct MockSDK;
+
+    impl ServiceSDK for MockSDK {
+        fn get_value(&self, _key: &str) -> Option<Bytes> {
+            None
+        }
+
+        fn set_value(&mut self, _key: &str, _value: Bytes) {}
+    }
+
+    fn test_ctx() -> ServiceContext {
+        ServiceContext::new(
+            Address::from_hex("0xcff1002107105460941f797828f468667aa1a2db").unwrap(),
+            1,
+            Hash::from_empty(),
+            "util".to_owned(),
+            "verify".to_owned(),
+        )
+    }
+
+    #[test]
+    fn keccak256_hashes_the_payload() {
+        let service = UtilService::new(MockSDK);
+        let payload = Bytes::from_static(b"huobi-chain");
+
+        let resp = service.keccak256(test_ctx(), Keccak256Payload {
+            payload: payload.clone(),
+        });
+
+        assert!(!resp.is_error());
+        assert_eq!(resp.succeed_data.hash, Hash::digest(payload));
+    }
+
+    #[test]
+    fn verify_accepts_a_matching_signature_and_pubkey() {
+        let priv_key = Secp256k1RecoverablePrivateKey::generate(&mut rand::thread_rng());
+        let pub_key = priv_key.pub_key();
+        let hash = Hash::digest(Bytes::from_static(b"transfer 10 to bob"));
+        let signature = priv_key.sign_message(hash.as_bytes());
+
+        let service = UtilService::new(MockSDK);
+        let resp = service.verify(test_ctx(), VerifyPayload {
+            hash,
+            signature: signature.to_bytes(),
+            pubkey: pub_key.to_bytes(),
+        });
+
+        assert!(!resp.is_error());
+        assert!(resp.succeed_data.is_valid);
+    }
+
+    #[test]
+    fn verify_rejects_a_signature_from_a_different_key() {
+        let signer = Secp256k1RecoverablePrivateKey::generate(&mut rand::thread_rng());
+        let other = Secp256k1RecoverablePrivateKey::generate(&mut rand::thread_rng());
+        let hash = Hash::digest(Bytes::from_static(b"transfer 10 to bob"));
+        let signature = signer.sign_message(hash.as_bytes());
+
+        let service = UtilService::new(MockSDK);
+        let resp = service.verify(test_ctx(), VerifyPayload {
+            hash,
+            signature: signature.to_bytes(),
+            pubkey: other.pub_key().to_bytes(),
+        });
+
+        assert!(!resp.is_error());
+        assert!(!resp.succeed_data.is_valid);
+    }
+
+    #[test]
+    fn verify_rejects_a_malformed_pubkey() {
+        let service = UtilService::new(MockSDK);
+        let resp = service.verify(test_ctx(), VerifyPayload {
+            hash:      Hash::digest(Bytes::from_static(b"x")),
+            signature: Bytes::from_static(&[0u8; 65]),
+            pubkey:    Bytes::from_static(&[1, 2, 3]),
+        });
+
+        assert!(resp.is_error());
+    }
+
+    #[test]
+    fn verify_rejects_a_malformed_signature() {
+        let pub_key = Secp256k1RecoverablePrivateKey::generate(&mut rand::thread_rng()).pub_key();
+
+        let service = UtilService::new(MockSDK);
+        let resp = service.verify(test_ctx(), VerifyPayload {
+            hash:      Hash::digest(Bytes::from_static(b"x")),
+            signature: Bytes::from_static(&[0u8; 3]),
+            pubkey:    pub_key.to_bytes(),
+        });
+
+        assert!(resp.is_error());
+    }
+}
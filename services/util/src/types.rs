@@ -0,0 +1,25 @@
+use bytes::Bytes;
+
+use protocol::types::Hash;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Keccak256Payload {
+    pub payload: Bytes,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Keccak256Response {
+    pub hash: Hash,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct VerifyPayload {
+    pub hash:      Hash,
+    pub signature: Bytes,
+    pub pubkey:    Bytes,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct VerifyResponse {
+    pub is_valid: bool,
+}
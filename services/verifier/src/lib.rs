@@ -0,0 +1,229 @@
+#[macro_use]
+extern crate serde_derive;
+
+pub mod types;
+
+use bytes::Bytes;
+
+use binding_macro::{read, service};
+use protocol::traits::{ServiceResponse, ServiceSDK};
+use protocol::types::{Hash, ServiceContext};
+
+use crate::types::{MerkleProof, StoredBatchInfo, VerifyBatchResponse};
+
+pub struct VerifierService<SDK> {
+    sdk: SDK,
+}
+
+#[service]
+impl<SDK: ServiceSDK> VerifierService<SDK> {
+    pub fn new(sdk: SDK) -> Self {
+        Self { sdk }
+    }
+
+    /// Recompute the rolling block hash by folding batch `n`'s transaction
+    /// hashes on top of batch `n - 1`'s proven last block hash, then check
+    /// the fold lands on the last block hash proven under batch `n`'s state
+    /// root.
+    #[read]
+    fn verify_batch(
+        &self,
+        _ctx: ServiceContext,
+        payload: StoredBatchInfo,
+    ) -> ServiceResponse<VerifyBatchResponse> {
+        if payload.tx_hashes.is_empty() {
+            return ServiceResponse::from_error(201, "batch payload is empty".to_owned());
+        }
+
+        let seed = match reconstruct_root(&payload.proof_n_minus_1) {
+            root if root == payload.state_root_n_minus_1 => payload.proof_n_minus_1.leaf.clone(),
+            _ => {
+                return ServiceResponse::from_error(
+                    202,
+                    "merkle proof for batch n - 1 does not match its state root".to_owned(),
+                )
+            }
+        };
+
+        let claimed_last_hash = match reconstruct_root(&payload.proof_n) {
+            root if root == payload.state_root_n => payload.proof_n.leaf.clone(),
+            _ => {
+                return ServiceResponse::from_error(
+                    203,
+                    "merkle proof for batch n does not match its state root".to_owned(),
+                )
+            }
+        };
+
+        let folded = payload
+            .tx_hashes
+            .iter()
+            .fold(seed, |h, tx_hash| fold_hash(&h, tx_hash));
+
+        if folded != claimed_last_hash {
+            return ServiceResponse::from_error(
+                204,
+                "rolling hash of the batch payload does not match the proven last block hash"
+                    .to_owned(),
+            );
+        }
+
+        ServiceResponse::from_succeed(VerifyBatchResponse { is_valid: true })
+    }
+}
+
+/// `h_{i+1} = keccak256(h_i || tx_hash_i)`.
+fn fold_hash(prev: &Hash, tx_hash: &Hash) -> Hash {
+    let mut buf = Vec::with_capacity(64);
+    buf.extend_from_slice(prev.as_bytes());
+    buf.extend_from_slice(tx_hash.as_bytes());
+    Hash::digest(Bytes::from(buf))
+}
+
+/// Recompute the merkle root above `proof.leaf`, climbing the sibling path
+/// bottom-up using `proof.index` to decide which side `leaf` sits on at
+/// each level.
+fn reconstruct_root(proof: &MerkleProof) -> Hash {
+    let mut acc = proof.leaf.clone();
+    let mut index = proof.index;
+
+    for sibling in &proof.path {
+        let mut buf = Vec::with_capacity(64);
+        if index % 2 == 0 {
+            buf.extend_from_slice(acc.as_bytes());
+            buf.extend_from_slice(sibling.as_bytes());
+        } else {
+            buf.extend_from_slice(sibling.as_bytes());
+            buf.extend_from_slice(acc.as_bytes());
+        }
+        acc = Hash::digest(Bytes::from(buf));
+        index /= 2;
+    }
+
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use protocol::types::Address;
+
+    use super::*;
+
+    struct MockSDK;
+
+    impl ServiceSDK for MockSDK {
+        fn get_value(&self, _key: &str) -> Option<Bytes> {
+            None
+        }
+
+        fn set_value(&mut self, _key: &str, _value: Bytes) {}
+    }
+
+    fn test_ctx() -> ServiceContext {
+        ServiceContext::new(
+            Address::from_hex("0xcff1002107105460941f797828f468667aa1a2db").unwrap(),
+            1,
+            Hash::from_empty(),
+            "verifier".to_owned(),
+            "verify_batch".to_owned(),
+        )
+    }
+
+    fn single_leaf_proof(leaf: Hash) -> (MerkleProof, Hash) {
+        let proof = MerkleProof {
+            leaf,
+            path: vec![],
+            index: 0,
+        };
+        let root = reconstruct_root(&proof);
+        (proof, root)
+    }
+
+    #[test]
+    fn verify_batch_accepts_a_valid_rolling_hash() {
+        let prev_last_hash = Hash::digest(Bytes::from_static(b"genesis-block"));
+        let (proof_n_minus_1, state_root_n_minus_1) = single_leaf_proof(prev_last_hash.clone());
+
+        let tx_hashes = vec![
+            Hash::digest(Bytes::from_static(b"tx-0")),
+            Hash::digest(Bytes::from_static(b"tx-1")),
+        ];
+        let last_hash = tx_hashes
+            .iter()
+            .fold(prev_last_hash, |h, tx_hash| fold_hash(&h, tx_hash));
+        let (proof_n, state_root_n) = single_leaf_proof(last_hash);
+
+        let service = VerifierService::new(MockSDK);
+        let resp = service.verify_batch(test_ctx(), StoredBatchInfo {
+            state_root_n,
+            state_root_n_minus_1,
+            proof_n,
+            proof_n_minus_1,
+            tx_hashes,
+        });
+
+        assert!(!resp.is_error());
+        assert!(resp.succeed_data.is_valid);
+    }
+
+    #[test]
+    fn verify_batch_rejects_an_empty_payload() {
+        let (proof, root) = single_leaf_proof(Hash::digest(Bytes::from_static(b"leaf")));
+
+        let service = VerifierService::new(MockSDK);
+        let resp = service.verify_batch(test_ctx(), StoredBatchInfo {
+            state_root_n: root.clone(),
+            state_root_n_minus_1: root,
+            proof_n: proof.clone(),
+            proof_n_minus_1: proof,
+            tx_hashes: vec![],
+        });
+
+        assert!(resp.is_error());
+    }
+
+    #[test]
+    fn verify_batch_rejects_a_proof_that_does_not_reconstruct_its_root() {
+        let (proof_n_minus_1, state_root_n_minus_1) =
+            single_leaf_proof(Hash::digest(Bytes::from_static(b"genesis-block")));
+
+        let proof_n = MerkleProof {
+            leaf:  Hash::digest(Bytes::from_static(b"tampered-leaf")),
+            path:  vec![Hash::digest(Bytes::from_static(b"sibling"))],
+            index: 0,
+        };
+        let wrong_root = Hash::digest(Bytes::from_static(b"not-the-real-root"));
+
+        let service = VerifierService::new(MockSDK);
+        let resp = service.verify_batch(test_ctx(), StoredBatchInfo {
+            state_root_n: wrong_root,
+            state_root_n_minus_1,
+            proof_n,
+            proof_n_minus_1,
+            tx_hashes: vec![Hash::digest(Bytes::from_static(b"tx-0"))],
+        });
+
+        assert!(resp.is_error());
+    }
+
+    #[test]
+    fn verify_batch_rejects_a_rolling_hash_mismatch() {
+        let prev_last_hash = Hash::digest(Bytes::from_static(b"genesis-block"));
+        let (proof_n_minus_1, state_root_n_minus_1) = single_leaf_proof(prev_last_hash);
+
+        // Claim a last-block hash that doesn't match folding the tx hashes.
+        let (proof_n, state_root_n) =
+            single_leaf_proof(Hash::digest(Bytes::from_static(b"unrelated-hash")));
+
+        let service = VerifierService::new(MockSDK);
+        let resp = service.verify_batch(test_ctx(), StoredBatchInfo {
+            state_root_n,
+            state_root_n_minus_1,
+            proof_n,
+            proof_n_minus_1,
+            tx_hashes: vec![Hash::digest(Bytes::from_static(b"tx-0"))],
+        });
+
+        assert!(resp.is_error());
+    }
+}
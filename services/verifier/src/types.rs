@@ -0,0 +1,32 @@
+use protocol::types::Hash;
+
+/// A merkle inclusion proof for the last block hash of a batch, rooted at
+/// the batch's state root.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MerkleProof {
+    /// The leaf being proven: the hash of the last block in the batch.
+    pub leaf:  Hash,
+    /// Sibling hashes from the leaf up to the root, bottom to top.
+    pub path:  Vec<Hash>,
+    /// The leaf's index in the tree, used to decide sibling ordering at
+    /// each level (even index -> leaf is the left child).
+    pub index: u32,
+}
+
+/// Witness proving that batch `n` rolls forward from batch `n - 1` without
+/// trusting a relayer: the two state roots, a merkle proof of the last
+/// block hash under each, and the ordered transaction hashes of batch `n`'s
+/// payload.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StoredBatchInfo {
+    pub state_root_n:         Hash,
+    pub state_root_n_minus_1: Hash,
+    pub proof_n:              MerkleProof,
+    pub proof_n_minus_1:      MerkleProof,
+    pub tx_hashes:            Vec<Hash>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct VerifyBatchResponse {
+    pub is_valid: bool,
+}
@@ -24,6 +24,8 @@ use multi_signature::MultiSignatureService;
 use riscv::RiscvService;
 use timestamp::TimestampService;
 use transfer_quota::TransferQuotaService;
+use util::UtilService;
+use verifier::VerifierService;
 
 type AuthorizationType<SDK> = AuthorizationService<
     AdmissionControlService<
@@ -315,6 +317,8 @@ impl ServiceMapping for MockServiceMapping {
             "timestamp" => Box::new(Self::new_timestamp(factory)?) as Box<dyn Service>,
             "transfer_quota" => Box::new(Self::new_transfer_quota(factory)?) as Box<dyn Service>,
             "riscv" => Box::new(Self::new_riscv(factory)?) as Box<dyn Service>,
+            "util" => Box::new(Self::new_util(factory)?) as Box<dyn Service>,
+            "verifier" => Box::new(Self::new_verifier(factory)?) as Box<dyn Service>,
             _ => panic!("not found service"),
         };
 
@@ -333,6 +337,8 @@ impl ServiceMapping for MockServiceMapping {
             "timestamp".to_owned(),
             "transfer_quota".to_owned(),
             "riscv".to_owned(),
+            "util".to_owned(),
+            "verifier".to_owned(),
         ]
     }
 }
@@ -353,6 +359,18 @@ impl MockServiceMapping {
         ))
     }
 
+    fn new_util<SDK: 'static + ServiceSDK, Factory: SDKFactory<SDK>>(
+        factory: &Factory,
+    ) -> ProtocolResult<UtilService<SDK>> {
+        Ok(UtilService::new(factory.get_sdk("util")?))
+    }
+
+    fn new_verifier<SDK: 'static + ServiceSDK, Factory: SDKFactory<SDK>>(
+        factory: &Factory,
+    ) -> ProtocolResult<VerifierService<SDK>> {
+        Ok(VerifierService::new(factory.get_sdk("verifier")?))
+    }
+
     fn new_transfer_quota<SDK: 'static + ServiceSDK, Factory: SDKFactory<SDK>>(
         factory: &Factory,
     ) -> ProtocolResult<TransferQuotaServiceType<SDK>> {